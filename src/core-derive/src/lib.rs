@@ -0,0 +1,245 @@
+// Copyright 2016 The Gfx-rs Developers.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derive macros for the `Pod` and `Zeroable` traits from `gfx_core::memory`.
+//!
+//! Hand-writing `unsafe impl Pod` is where soundness bugs creep in: a
+//! `#[repr(C)]` struct with internal padding has undefined padding bytes
+//! and must never be treated as `Pod`. The derives here reject that at
+//! compile time instead of trusting the author.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate syn;
+#[macro_use]
+extern crate quote;
+
+use proc_macro::TokenStream;
+use syn::{Data, DeriveInput, Fields};
+
+/// Derive `Zeroable` for a `#[repr(C)]` or `#[repr(transparent)]` struct
+/// whose fields are all themselves `Zeroable`.
+#[proc_macro_derive(Zeroable)]
+pub fn derive_zeroable(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let fields = struct_fields(&input, "Zeroable");
+    assert_repr_c_or_transparent(&input, "Zeroable");
+
+    let field_checks = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote! { let _: fn() = || { fn check<T: gfx_core::memory::Zeroable>() {} check::<#ty>(); }; }
+    });
+
+    let expanded = quote! {
+        unsafe impl gfx_core::memory::Zeroable for #name {
+            fn zeroed() -> Self {
+                #( #field_checks )*
+                unsafe { ::std::mem::zeroed() }
+            }
+        }
+    };
+    expanded.into()
+}
+
+/// Derive `Pod` (and, implicitly, `Zeroable`) for a `#[repr(C)]` or
+/// `#[repr(transparent)]` struct whose fields are all themselves `Pod`
+/// and which contains no padding.
+#[proc_macro_derive(Pod)]
+pub fn derive_pod(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let fields = struct_fields(&input, "Pod");
+    assert_repr_c_or_transparent(&input, "Pod");
+
+    let field_checks = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote! { let _: fn() = || { fn check<T: gfx_core::memory::Pod>() {} check::<#ty>(); }; }
+    });
+    let field_sizes = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote! { ::std::mem::size_of::<#ty>() }
+    });
+
+    let expanded = quote! {
+        unsafe impl gfx_core::memory::Zeroable for #name {}
+
+        unsafe impl gfx_core::memory::Pod for #name {
+            // A padding byte is not owned by any field, so if the struct's
+            // size is larger than the sum of its fields' sizes, there must
+            // be padding and the struct cannot be `Pod`.
+        }
+
+        const _: () = {
+            #( #field_checks )*
+            const _ASSERT_NO_PADDING: () = assert!(
+                ::std::mem::size_of::<#name>() == 0 #( + #field_sizes )*,
+                concat!(stringify!(#name), " has padding and cannot derive Pod"),
+            );
+        };
+    };
+    expanded.into()
+}
+
+/// Derive `Contiguous` for a fieldless, unit-only enum with an explicit
+/// `#[repr(_)]` integer type.
+///
+/// `MIN_VALUE`/`MAX_VALUE` are computed from the enum's actual
+/// discriminants (explicit `= N`, or the usual implicit "previous + 1"),
+/// not assumed to start at zero. The derive rejects enums whose
+/// discriminants have gaps or duplicates, since `Contiguous`'s default
+/// `from_integer` trusts every integer in `MIN_VALUE..=MAX_VALUE` to be a
+/// valid discriminant and `mem::transmute_copy`s it without re-checking.
+#[proc_macro_derive(Contiguous)]
+pub fn derive_contiguous(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let int_ty = repr_int(&input);
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => panic!("#[derive(Contiguous)] only supports fieldless enums"),
+    };
+    if variants.is_empty() {
+        panic!("#[derive(Contiguous)] requires at least one variant");
+    }
+
+    let mut next_value = 0i128;
+    let mut values = Vec::with_capacity(variants.len());
+    for variant in variants {
+        if variant.fields != Fields::Unit {
+            panic!("#[derive(Contiguous)] only supports fieldless enums");
+        }
+        let value = match &variant.discriminant {
+            Some((_, expr)) => eval_discriminant(expr),
+            None => next_value,
+        };
+        next_value = value + 1;
+        values.push(value);
+    }
+
+    let mut sorted = values.clone();
+    sorted.sort();
+    sorted.dedup();
+    if sorted.len() != values.len() || sorted.windows(2).any(|w| w[1] - w[0] != 1) {
+        panic!(
+            "#[derive(Contiguous)] requires variant discriminants to form \
+             a contiguous run with no gaps or duplicates"
+        );
+    }
+
+    let min_value = int_literal(*sorted.first().unwrap());
+    let max_value = int_literal(*sorted.last().unwrap());
+
+    let expanded = quote! {
+        unsafe impl gfx_core::memory::Contiguous for #name {
+            type Int = #int_ty;
+            const MIN_VALUE: #int_ty = #min_value;
+            const MAX_VALUE: #int_ty = #max_value;
+        }
+    };
+    expanded.into()
+}
+
+/// Evaluate an explicit enum discriminant (`Variant = EXPR`) at derive
+/// time. Only plain (possibly negated) integer literals are supported;
+/// any other constant expression makes the derive panic.
+fn eval_discriminant(expr: &syn::Expr) -> i128 {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit
+            .base10_parse::<i128>()
+            .expect("#[derive(Contiguous)] discriminant literal does not fit in i128"),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => -eval_discriminant(expr),
+        _ => panic!(
+            "#[derive(Contiguous)] requires explicit discriminants to be integer literals"
+        ),
+    }
+}
+
+/// Render a compile-time-computed `i128` as the token for an integer
+/// literal of the enum's `#[repr(_)]` type, e.g. `-1` or `4`.
+fn int_literal(value: i128) -> proc_macro2::TokenStream {
+    let lit = syn::LitInt::new(&value.unsigned_abs().to_string(), proc_macro2::Span::call_site());
+    if value < 0 {
+        quote! { -#lit }
+    } else {
+        quote! { #lit }
+    }
+}
+
+fn repr_int(input: &DeriveInput) -> syn::Ident {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+        let mut found = None;
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                let name = ident.to_string();
+                if matches!(
+                    name.as_str(),
+                    "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64"
+                        | "isize"
+                ) {
+                    found = Some(ident.clone());
+                }
+            }
+            Ok(())
+        });
+        if let Some(ident) = found {
+            return ident;
+        }
+    }
+    panic!("#[derive(Contiguous)] requires an explicit #[repr(_)] integer type");
+}
+
+fn struct_fields<'a>(input: &'a DeriveInput, trait_name: &str) -> Vec<&'a syn::Field> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        _ => panic!("#[derive({})] only supports structs", trait_name),
+    }
+}
+
+fn assert_repr_c_or_transparent(input: &DeriveInput, trait_name: &str) {
+    let ok = input.attrs.iter().any(|attr| {
+        if !attr.path().is_ident("repr") {
+            return false;
+        }
+        let mut found = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") || meta.path.is_ident("transparent") {
+                found = true;
+            }
+            Ok(())
+        });
+        found
+    });
+    if !ok {
+        panic!(
+            "#[derive({})] requires #[repr(C)] or #[repr(transparent)]",
+            trait_name
+        );
+    }
+}
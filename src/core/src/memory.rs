@@ -36,6 +36,28 @@ pub enum Usage {
     CpuOnly(Access),
 }
 
+impl Usage {
+    /// The raw `#[repr(u8)]` discriminant of this value, e.g. to store
+    /// alongside a serialized `Access` when writing pipeline state to a
+    /// file.
+    ///
+    /// `Usage` carries an `Access` payload on two of its variants, so it
+    /// cannot implement `Contiguous` soundly (that trait's default
+    /// `from_integer` would have to `transmute_copy` a bare discriminant
+    /// into a full `Usage`, fabricating an `Access` that was never there).
+    /// This accessor and `is_valid_discriminant` expose the part of that
+    /// round-trip that *is* sound: reading the tag back out, and checking
+    /// a raw tag read from a file before trusting it.
+    pub fn discriminant(&self) -> u8 {
+        unsafe { *(self as *const Usage as *const u8) }
+    }
+
+    /// Whether `tag` is one of `Usage`'s five valid discriminants.
+    pub fn is_valid_discriminant(tag: u8) -> bool {
+        tag <= 4
+    }
+}
+
 bitflags!(
     /// Memory access
     pub flags Access: u8 {
@@ -74,15 +96,62 @@ pub trait Typed: Sized {
     fn raw(&self) -> &Self::Raw;
 }
 
+/// A trait for types that can be safely created from an all-zero bit
+/// pattern.
+///
+/// This is weaker than `Pod`: a type can be validly zeroed without every
+/// bit pattern being valid for it.
+pub unsafe trait Zeroable: Sized {
+    /// Produce a zeroed instance of this type.
+    fn zeroed() -> Self {
+        unsafe { mem::zeroed() }
+    }
+}
+
+/// A trait for enums that map to a contiguous, checked range of an integer
+/// representation.
+///
+/// This lets code that reads raw integers back out of a file or a
+/// GPU-visible descriptor recover the enum they came from, rejecting
+/// discriminants that were never valid rather than transmuting blindly.
+pub unsafe trait Contiguous: Copy {
+    /// The integer type used to represent `Self`.
+    type Int: Pod + Ord;
+    /// The smallest valid integer representation of `Self`.
+    const MIN_VALUE: Self::Int;
+    /// The largest valid integer representation of `Self`.
+    const MAX_VALUE: Self::Int;
+
+    /// Recover a `Self` from its integer representation, or `None` if `i`
+    /// is outside `MIN_VALUE..=MAX_VALUE`.
+    fn from_integer(i: Self::Int) -> Option<Self> {
+        if i >= Self::MIN_VALUE && i <= Self::MAX_VALUE {
+            Some(unsafe { mem::transmute_copy(&i) })
+        } else {
+            None
+        }
+    }
+
+    /// Get the integer representation of `self`.
+    fn into_integer(self) -> Self::Int {
+        unsafe { mem::transmute_copy(&self) }
+    }
+}
+
 /// A trait for plain-old-data types.
 ///
 /// A POD type does not have invalid bit patterns and can be safely
 /// created from arbitrary bit pattern.
-pub unsafe trait Pod {}
+pub unsafe trait Pod: Zeroable {}
 
 macro_rules! impl_pod {
-    ( ty = $($ty:ty)* ) => { $( unsafe impl Pod for $ty {} )* };
-    ( ar = $($tt:expr)* ) => { $( unsafe impl<T: Pod> Pod for [T; $tt] {} )* };
+    ( ty = $($ty:ty)* ) => { $( unsafe impl Zeroable for $ty {} unsafe impl Pod for $ty {} )* };
+    ( ar = $($tt:expr)* ) => {
+        $(
+            unsafe impl<T: Zeroable> Zeroable for [T; $tt] {}
+            unsafe impl<T: Pod> Pod for [T; $tt] {}
+        )*
+    };
 }
 
 impl_pod! { ty = isize usize i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 }
@@ -90,14 +159,822 @@ impl_pod! { ar =
     0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32
 }
 
+/// Fill a slice of `Zeroable` values with zeroes, e.g. before uploading it
+/// as a staging buffer.
+pub fn fill_zeroes<T: Zeroable>(slice: &mut [T]) {
+    for item in slice.iter_mut() {
+        *item = T::zeroed();
+    }
+}
+
+/// The error type returned when a cast between `Pod` types cannot be
+/// performed safely.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PodCastError {
+    /// The alignment required by the target type is greater than that of
+    /// the input, and the input slice is not actually aligned for it.
+    TargetAlignmentGreaterAndInputNotAligned,
+    /// The output slice would have ended on a byte that isn't a multiple of
+    /// `size_of::<B>()`, i.e. there would be trailing bytes left over.
+    OutputSliceWouldHaveSlop,
+    /// One of the involved types is zero-sized while the other is not, so
+    /// there is no sensible length to give the output slice; or the byte
+    /// slice passed to `try_from_bytes` is not exactly `size_of::<T>()`
+    /// bytes long.
+    SizeMismatch,
+    /// The byte slice passed to `try_from_bytes` is not aligned for the
+    /// target type.
+    AlignmentMismatch,
+}
+
+/// Cast a slice from one POD type to another, checking size and alignment
+/// rather than panicking.
+pub fn try_cast_slice<A: Pod, B: Pod>(slice: &[A]) -> Result<&[B], PodCastError> {
+    use std::slice;
+
+    let len = cast_slice_len::<A, B>(slice.as_ptr() as *const u8, slice.len())?;
+    Ok(unsafe { slice::from_raw_parts(slice.as_ptr() as *const B, len) })
+}
+
+/// Work out the output length of a slice-to-slice cast, or the
+/// `PodCastError` that rules it out. Shared by `try_cast_slice` and
+/// `checked::try_cast_slice`, which place different trait bounds on `A`
+/// and `B` but need the same size/alignment arithmetic.
+fn cast_slice_len<A, B>(ptr: *const u8, len: usize) -> Result<usize, PodCastError> {
+    if mem::align_of::<B>() > mem::align_of::<A>() && (ptr as usize) % mem::align_of::<B>() != 0 {
+        return Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned);
+    }
+    if (mem::size_of::<A>() == 0) != (mem::size_of::<B>() == 0) {
+        return Err(PodCastError::SizeMismatch);
+    }
+    let raw_len = mem::size_of::<A>().wrapping_mul(len);
+    if mem::size_of::<B>() == 0 {
+        Ok(len)
+    } else if raw_len % mem::size_of::<B>() != 0 {
+        Err(PodCastError::OutputSliceWouldHaveSlop)
+    } else {
+        Ok(raw_len / mem::size_of::<B>())
+    }
+}
+
+/// Cast a mutable slice from one POD type to another, checking size and
+/// alignment rather than panicking.
+pub fn try_cast_slice_mut<A: Pod, B: Pod>(slice: &mut [A]) -> Result<&mut [B], PodCastError> {
+    use std::slice;
+
+    // Borrow as a shared slice first to reuse the validation above, then
+    // rebuild the pointer as mutable; the source borrow has already ended.
+    let len = try_cast_slice::<A, B>(slice)?.len();
+    Ok(unsafe { slice::from_raw_parts_mut(slice.as_mut_ptr() as *mut B, len) })
+}
+
 /// Cast a slice from one POD type to another.
+///
+/// # Panics
+///
+/// Panics if the cast is not possible; see `try_cast_slice` for a
+/// non-panicking version.
 pub fn cast_slice<A: Pod, B: Pod>(slice: &[A]) -> &[B] {
+    match try_cast_slice(slice) {
+        Ok(slice) => slice,
+        Err(e) => panic!("cast_slice: {:?}", e),
+    }
+}
+
+/// Cast a mutable slice from one POD type to another.
+///
+/// # Panics
+///
+/// Panics if the cast is not possible; see `try_cast_slice_mut` for a
+/// non-panicking version.
+pub fn cast_slice_mut<A: Pod, B: Pod>(slice: &mut [A]) -> &mut [B] {
+    match try_cast_slice_mut(slice) {
+        Ok(slice) => slice,
+        Err(e) => panic!("cast_slice_mut: {:?}", e),
+    }
+}
+
+/// View a `Pod` value as a byte slice, e.g. to `memcpy` one uniform block
+/// into an upload buffer.
+pub fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    use std::slice;
+
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, mem::size_of::<T>()) }
+}
+
+/// View a `Pod` value as a mutable byte slice.
+pub fn bytes_of_mut<T: Pod>(value: &mut T) -> &mut [u8] {
     use std::slice;
 
-    let raw_len = mem::size_of::<A>().wrapping_mul(slice.len());
-    let len = raw_len / mem::size_of::<B>();
-    assert_eq!(raw_len, mem::size_of::<B>().wrapping_mul(len));
-    unsafe {
-        slice::from_raw_parts(slice.as_ptr() as *const B, len)
+    unsafe { slice::from_raw_parts_mut(value as *mut T as *mut u8, mem::size_of::<T>()) }
+}
+
+/// Reinterpret a byte slice as a `Pod` value, checking size and alignment
+/// rather than panicking.
+pub fn try_from_bytes<T: Pod>(bytes: &[u8]) -> Result<&T, PodCastError> {
+    if bytes.len() != mem::size_of::<T>() {
+        Err(PodCastError::SizeMismatch)
+    } else if (bytes.as_ptr() as usize) % mem::align_of::<T>() != 0 {
+        Err(PodCastError::AlignmentMismatch)
+    } else {
+        Ok(unsafe { &*(bytes.as_ptr() as *const T) })
+    }
+}
+
+/// Reinterpret a byte slice as a `Pod` value.
+///
+/// # Panics
+///
+/// Panics if the cast is not possible; see `try_from_bytes` for a
+/// non-panicking version.
+pub fn from_bytes<T: Pod>(bytes: &[u8]) -> &T {
+    match try_from_bytes(bytes) {
+        Ok(value) => value,
+        Err(e) => panic!("from_bytes: {:?}", e),
+    }
+}
+
+/// A trait for types with no padding or uninitialized bytes, but which may
+/// not have every bit pattern be valid (unlike `Pod`). Every `Pod` type is
+/// trivially `NoUninit`.
+pub unsafe trait NoUninit {}
+
+unsafe impl<T: Pod> NoUninit for T {}
+
+/// A trait for types where every bit pattern is valid, but which may not
+/// meet the rest of the `Pod` contract (e.g. `mem::MaybeUninit<T>`). Every
+/// `Pod` type is trivially `AnyBitPattern`.
+pub unsafe trait AnyBitPattern: Zeroable {}
+
+unsafe impl<T: Pod> AnyBitPattern for T {}
+
+/// A trait for types that are not themselves `Pod` because some bit
+/// patterns are invalid (`bool`, `char`), but which can be safely read
+/// from raw bytes after validation.
+///
+/// # Safety
+///
+/// `Self::Bits` must have the exact same size and alignment as `Self` —
+/// `checked::try_cast_slice` reinterprets the very same pointer and
+/// element count it computed for `Self::Bits` as a `&[Self]`, so any
+/// mismatch is an out-of-bounds read. This rules out fieldless-looking but
+/// data-carrying enums (e.g. a `#[repr(u8)]` enum with a payload variant)
+/// unless `Bits` is chosen to match their real layout, tag and payload
+/// together.
+pub unsafe trait CheckedBitPattern: Copy {
+    /// An `AnyBitPattern` type with the same layout as `Self`, used to read
+    /// the raw bits before they are validated.
+    type Bits: AnyBitPattern;
+
+    /// Whether `bits` is a valid bit pattern for `Self`.
+    fn is_valid_bit_pattern(bits: &Self::Bits) -> bool;
+}
+
+unsafe impl CheckedBitPattern for bool {
+    type Bits = u8;
+
+    fn is_valid_bit_pattern(bits: &u8) -> bool {
+        *bits == 0 || *bits == 1
+    }
+}
+
+unsafe impl CheckedBitPattern for char {
+    type Bits = u32;
+
+    fn is_valid_bit_pattern(bits: &u32) -> bool {
+        ::std::char::from_u32(*bits).is_some()
+    }
+}
+
+/// Bit-for-bit layout of `Usage`: a `#[repr(u8)]` discriminant followed by
+/// the `Access` byte that `Persistent`/`CpuOnly` carry (unused, and so of
+/// unspecified value, for the other three variants). `#[repr(C)]` with two
+/// `u8` fields gives this the same size and alignment as `Usage` itself,
+/// which `CheckedBitPattern::Bits` requires.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub struct UsageBits {
+    tag: u8,
+    access: u8,
+}
+
+unsafe impl Zeroable for UsageBits {}
+unsafe impl Pod for UsageBits {}
+
+unsafe impl CheckedBitPattern for Usage {
+    type Bits = UsageBits;
+
+    fn is_valid_bit_pattern(bits: &UsageBits) -> bool {
+        // Only `Persistent` (3) and `CpuOnly` (4) carry an `Access` byte.
+        Usage::is_valid_discriminant(bits.tag) && (bits.tag < 3 || bits.access <= Access::RW.bits())
+    }
+}
+
+/// Casts that validate bit patterns rather than requiring a full `Pod`
+/// bound, for types like `bool`/`char`/`Usage` that have some invalid bit
+/// patterns (e.g. reading them back from a GPU readback buffer).
+pub mod checked {
+    use super::{AnyBitPattern, CheckedBitPattern, NoUninit, PodCastError};
+    use std::{fmt, mem, slice};
+
+    /// The error type returned when a checked cast cannot be performed.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum CheckedCastError {
+        /// The cast failed for the same reasons `try_cast_slice` can fail.
+        PodCastError(PodCastError),
+        /// The cast's size and alignment were fine, but one of the
+        /// elements was not a valid bit pattern for the target type.
+        InvalidBitPattern,
+    }
+
+    impl fmt::Display for CheckedCastError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Debug::fmt(self, f)
+        }
+    }
+
+    /// Cast a slice of `NoUninit` values to a slice of `CheckedBitPattern`
+    /// values, validating every element's bit pattern rather than
+    /// assuming it (as `try_cast_slice` would require `B: Pod` to do).
+    pub fn try_cast_slice<A: NoUninit, B: CheckedBitPattern>(
+        slice: &[A],
+    ) -> Result<&[B], CheckedCastError> {
+        // `CheckedBitPattern::Bits` must describe `Self`'s exact layout, or
+        // the `from_raw_parts` below (same pointer/len as for `B::Bits`,
+        // reinterpreted as `B`) would read out of bounds. This is part of
+        // `CheckedBitPattern`'s safety contract, so a violation is a bug in
+        // the `unsafe impl`, not a normal error for callers to handle.
+        assert_eq!(
+            mem::size_of::<B>(),
+            mem::size_of::<B::Bits>(),
+            "CheckedBitPattern::Bits must have the same size as Self"
+        );
+        assert_eq!(
+            mem::align_of::<B>(),
+            mem::align_of::<B::Bits>(),
+            "CheckedBitPattern::Bits must have the same alignment as Self"
+        );
+
+        let len = super::cast_slice_len::<A, B::Bits>(slice.as_ptr() as *const u8, slice.len())
+            .map_err(CheckedCastError::PodCastError)?;
+        let bits: &[B::Bits] =
+            unsafe { slice::from_raw_parts(slice.as_ptr() as *const B::Bits, len) };
+        if bits.iter().all(B::is_valid_bit_pattern) {
+            Ok(unsafe { slice::from_raw_parts(slice.as_ptr() as *const B, len) })
+        } else {
+            Err(CheckedCastError::InvalidBitPattern)
+        }
+    }
+}
+
+/// Sub-allocates device memory instead of handing every request straight
+/// to the driver, picking a strategy from the `Usage` hint.
+pub mod heaps {
+    use super::{Access, Usage};
+
+    /// Opaque id of one underlying driver-level memory allocation.
+    pub type MemoryId = usize;
+
+    /// A sub-allocated range within one driver-level allocation.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct MemoryBlock {
+        /// The backing driver-level allocation this block was carved from.
+        pub memory_id: MemoryId,
+        /// Byte offset of the block within that allocation.
+        pub offset: u64,
+        /// Size of the block in bytes.
+        pub size: u64,
+    }
+
+    /// Utilization stats for a single heap.
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct HeapStats {
+        /// Bytes currently handed out to live allocations.
+        pub used_bytes: u64,
+        /// Total bytes backing the heap.
+        pub allocated_bytes: u64,
+        /// How scattered the heap's free space is, in `0.0..=1.0`: `0.0`
+        /// means all free bytes form one contiguous range (no fragment is
+        /// too small to satisfy a large allocation), `1.0` means the free
+        /// bytes are maximally split up. Computed as `1 -
+        /// largest_free_block / total_free`, so it is independent of how
+        /// full the heap is — a heap that is merely half-used, with that
+        /// half free in one block, reports `0.0`.
+        pub fragmentation: f32,
+    }
+
+    /// Total and largest-contiguous free bytes in a heap, used to compute
+    /// `HeapStats::fragmentation`.
+    struct FreeSpace {
+        total: u64,
+        largest: u64,
+    }
+
+    impl FreeSpace {
+        fn fragmentation(&self) -> f32 {
+            if self.total == 0 {
+                0.0
+            } else {
+                1.0 - (self.largest as f32 / self.total as f32)
+            }
+        }
+    }
+
+    fn align_up(value: u64, align: u64) -> u64 {
+        (value + align - 1) / align * align
+    }
+
+    /// A bump allocator for transient, per-frame memory: the offset
+    /// pointer resets to zero in one `O(1)` step instead of freeing each
+    /// allocation individually.
+    struct LinearAllocator {
+        memory_id: MemoryId,
+        capacity: u64,
+        cursor: u64,
+    }
+
+    impl LinearAllocator {
+        fn new(memory_id: MemoryId, capacity: u64) -> Self {
+            LinearAllocator {
+                memory_id,
+                capacity,
+                cursor: 0,
+            }
+        }
+
+        fn alloc(&mut self, size: u64, align: u64) -> Option<MemoryBlock> {
+            let offset = align_up(self.cursor, align);
+            if offset + size > self.capacity {
+                return None;
+            }
+            self.cursor = offset + size;
+            Some(MemoryBlock {
+                memory_id: self.memory_id,
+                offset,
+                size,
+            })
+        }
+
+        fn reset(&mut self) {
+            self.cursor = 0;
+        }
+
+        // The unused tail past the cursor is always one contiguous range.
+        fn free_space(&self) -> FreeSpace {
+            let free = self.capacity - self.cursor;
+            FreeSpace {
+                total: free,
+                largest: free,
+            }
+        }
+    }
+
+    /// A free-list allocator for long-lived resources: requested sizes are
+    /// rounded up to a power of two on alloc and carved from the first free
+    /// range that fits; freed ranges are coalesced with their neighbors.
+    struct FreeListAllocator {
+        memory_id: MemoryId,
+        // (offset, size) of each free range. `coalesce` sorts them by
+        // offset; `alloc` appends split-off ranges at the end unsorted.
+        free_blocks: Vec<(u64, u64)>,
+    }
+
+    impl FreeListAllocator {
+        fn new(memory_id: MemoryId, capacity: u64) -> Self {
+            FreeListAllocator {
+                memory_id,
+                free_blocks: vec![(0, capacity)],
+            }
+        }
+
+        fn alloc(&mut self, size: u64, align: u64) -> Option<MemoryBlock> {
+            let size = size.next_power_of_two();
+            let pos = self.free_blocks.iter().position(|&(offset, block_size)| {
+                align_up(offset, align) + size <= offset + block_size
+            })?;
+            let (offset, block_size) = self.free_blocks.remove(pos);
+            let aligned = align_up(offset, align);
+            if aligned > offset {
+                self.free_blocks.push((offset, aligned - offset));
+            }
+            let remainder = offset + block_size - aligned - size;
+            if remainder > 0 {
+                self.free_blocks.push((aligned + size, remainder));
+            }
+            Some(MemoryBlock {
+                memory_id: self.memory_id,
+                offset: aligned,
+                size,
+            })
+        }
+
+        fn free(&mut self, block: MemoryBlock) {
+            self.free_blocks.push((block.offset, block.size));
+            self.coalesce();
+        }
+
+        /// Merge adjacent free ranges back together so large allocations
+        /// can still be satisfied after a long string of frees.
+        fn coalesce(&mut self) {
+            self.free_blocks.sort_by_key(|&(offset, _)| offset);
+            let mut merged: Vec<(u64, u64)> = Vec::with_capacity(self.free_blocks.len());
+            for &(offset, size) in &self.free_blocks {
+                match merged.last_mut() {
+                    Some(last) if last.0 + last.1 == offset => last.1 += size,
+                    _ => merged.push((offset, size)),
+                }
+            }
+            self.free_blocks = merged;
+        }
+
+        fn free_space(&self) -> FreeSpace {
+            let total = self.free_blocks.iter().map(|&(_, size)| size).sum();
+            let largest = self
+                .free_blocks
+                .iter()
+                .map(|&(_, size)| size)
+                .max()
+                .unwrap_or(0);
+            FreeSpace { total, largest }
+        }
+    }
+
+    enum Allocator {
+        Linear(LinearAllocator),
+        FreeList(FreeListAllocator),
+    }
+
+    impl Allocator {
+        fn free_space(&self) -> FreeSpace {
+            match self {
+                Allocator::Linear(a) => a.free_space(),
+                Allocator::FreeList(a) => a.free_space(),
+            }
+        }
+    }
+
+    struct Heap {
+        allocator: Allocator,
+        used_bytes: u64,
+        allocated_bytes: u64,
+    }
+
+    /// Hands out `MemoryBlock`s sub-allocated from a small number of
+    /// driver-level heaps, instead of making one driver allocation per
+    /// resource.
+    #[derive(Default)]
+    pub struct Heaps {
+        heaps: Vec<Heap>,
+    }
+
+    impl Heaps {
+        /// Create an empty set of heaps.
+        pub fn new() -> Self {
+            Heaps { heaps: Vec::new() }
+        }
+
+        /// Register a new `capacity`-byte backing allocation for `usage`,
+        /// returning the `MemoryId` later passed to `alloc`/`free`.
+        ///
+        /// `Dynamic` and `CpuOnly` usages get a linear allocator, since
+        /// that staging memory is written once per frame and reclaimed in
+        /// bulk. Everything else gets a free-list allocator, since it is
+        /// expected to live for an arbitrary, unpredictable duration.
+        pub fn add_heap(&mut self, usage: Usage, capacity: u64) -> MemoryId {
+            let memory_id = self.heaps.len();
+            let allocator = match usage {
+                Usage::Dynamic | Usage::CpuOnly(_) => {
+                    Allocator::Linear(LinearAllocator::new(memory_id, capacity))
+                }
+                Usage::GpuOnly | Usage::Immutable | Usage::Persistent(_) => {
+                    Allocator::FreeList(FreeListAllocator::new(memory_id, capacity))
+                }
+            };
+            self.heaps.push(Heap {
+                allocator,
+                used_bytes: 0,
+                allocated_bytes: capacity,
+            });
+            memory_id
+        }
+
+        /// Sub-allocate `size` bytes aligned to `align` from the heap
+        /// `memory_id`, or `None` if it doesn't fit.
+        pub fn alloc(&mut self, memory_id: MemoryId, size: u64, align: u64) -> Option<MemoryBlock> {
+            let heap = self.heaps.get_mut(memory_id)?;
+            let block = match &mut heap.allocator {
+                Allocator::Linear(a) => a.alloc(size, align),
+                Allocator::FreeList(a) => a.alloc(size, align),
+            }?;
+            heap.used_bytes += block.size;
+            Some(block)
+        }
+
+        /// Free a block previously returned by `alloc`. A no-op on linear
+        /// heaps, which are only ever reclaimed in bulk by `reset_frame`.
+        pub fn free(&mut self, memory_id: MemoryId, block: MemoryBlock) {
+            if let Some(heap) = self.heaps.get_mut(memory_id) {
+                if let Allocator::FreeList(a) = &mut heap.allocator {
+                    heap.used_bytes = heap.used_bytes.saturating_sub(block.size);
+                    a.free(block);
+                }
+            }
+        }
+
+        /// Reset every linear heap's cursor, implicitly freeing everything
+        /// allocated from it since the last reset.
+        pub fn reset_frame(&mut self) {
+            for heap in &mut self.heaps {
+                if let Allocator::Linear(a) = &mut heap.allocator {
+                    a.reset();
+                    heap.used_bytes = 0;
+                }
+            }
+        }
+
+        /// Current utilization of the heap backing `memory_id`.
+        pub fn stats(&self, memory_id: MemoryId) -> Option<HeapStats> {
+            self.heaps.get(memory_id).map(|heap| HeapStats {
+                used_bytes: heap.used_bytes,
+                allocated_bytes: heap.allocated_bytes,
+                fragmentation: heap.allocator.free_space().fragmentation(),
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn linear_alloc_and_reset() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::Dynamic, 16);
+
+            let a = heaps.alloc(id, 10, 1).unwrap();
+            assert_eq!(a, MemoryBlock { memory_id: id, offset: 0, size: 10 });
+            assert!(heaps.alloc(id, 10, 1).is_none());
+            assert_eq!(heaps.stats(id).unwrap().used_bytes, 10);
+
+            heaps.reset_frame();
+            assert_eq!(heaps.stats(id).unwrap().used_bytes, 0);
+            let b = heaps.alloc(id, 10, 1).unwrap();
+            assert_eq!(b.offset, 0);
+        }
+
+        #[test]
+        fn linear_alloc_respects_alignment() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::CpuOnly(Access::empty()), 32);
+
+            heaps.alloc(id, 3, 1).unwrap();
+            let b = heaps.alloc(id, 4, 8).unwrap();
+            assert_eq!(b.offset, 8);
+        }
+
+        #[test]
+        fn linear_heap_is_never_fragmented() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::Dynamic, 16);
+            heaps.alloc(id, 8, 1).unwrap();
+            assert_eq!(heaps.stats(id).unwrap().fragmentation, 0.0);
+        }
+
+        #[test]
+        fn free_list_alloc_rounds_up_to_power_of_two() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::GpuOnly, 64);
+
+            let block = heaps.alloc(id, 5, 1).unwrap();
+            assert_eq!(block.size, 8);
+        }
+
+        #[test]
+        fn free_list_alloc_exhausts_capacity() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::Immutable, 16);
+
+            assert!(heaps.alloc(id, 16, 1).is_some());
+            assert!(heaps.alloc(id, 1, 1).is_none());
+        }
+
+        #[test]
+        fn free_list_pads_for_alignment_without_losing_bytes() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::GpuOnly, 64);
+
+            // A one-byte allocation forces the next one to pad out to an
+            // 8-byte alignment; that padding must come back as free space.
+            heaps.alloc(id, 1, 1).unwrap();
+            let b = heaps.alloc(id, 4, 8).unwrap();
+            assert_eq!(b.offset, 8);
+            heaps.free(id, b);
+
+            let stats = heaps.stats(id).unwrap();
+            assert_eq!(stats.allocated_bytes - stats.used_bytes, 63);
+            // The padding bytes 1..8 are free again, so the next fit starts
+            // right after the first allocation.
+            let c = heaps.alloc(id, 4, 1).unwrap();
+            assert_eq!(c.offset, 1);
+        }
+
+        #[test]
+        fn free_list_coalesces_neighbors_on_free() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::GpuOnly, 32);
+
+            let a = heaps.alloc(id, 8, 1).unwrap();
+            let b = heaps.alloc(id, 8, 1).unwrap();
+            // One contiguous 16-byte hole remains; fragmentation is 0.
+            assert_eq!(heaps.stats(id).unwrap().fragmentation, 0.0);
+
+            heaps.free(id, a);
+            heaps.free(id, b);
+            // Everything coalesced back into one free range spanning the
+            // whole heap, so a full-size allocation must succeed again.
+            assert!(heaps.alloc(id, 32, 1).is_some());
+        }
+
+        #[test]
+        fn free_list_reports_fragmentation_when_scattered() {
+            let mut heaps = Heaps::new();
+            let id = heaps.add_heap(Usage::GpuOnly, 32);
+
+            let a = heaps.alloc(id, 8, 1).unwrap();
+            let _b = heaps.alloc(id, 8, 1).unwrap();
+            let _c = heaps.alloc(id, 8, 1).unwrap();
+            let d = heaps.alloc(id, 8, 1).unwrap();
+
+            // Free two non-adjacent blocks: 16 bytes free, but split into
+            // two 8-byte holes rather than one contiguous one.
+            heaps.free(id, a);
+            heaps.free(id, d);
+
+            let stats = heaps.stats(id).unwrap();
+            assert_eq!(stats.fragmentation, 0.5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cast_slice_same_size_round_trips() {
+        let floats: [f32; 2] = [1.0, 2.0];
+        let bytes: &[u8] = cast_slice(&floats[..]);
+        assert_eq!(bytes.len(), 8);
+        let back: &[f32] = cast_slice(bytes);
+        assert_eq!(back, &floats[..]);
+    }
+
+    #[test]
+    fn cast_slice_rejects_slop() {
+        let bytes: [u8; 5] = [0; 5];
+        assert_eq!(
+            try_cast_slice::<u8, u32>(&bytes),
+            Err(PodCastError::OutputSliceWouldHaveSlop)
+        );
+    }
+
+    #[test]
+    fn cast_slice_rejects_misaligned_input() {
+        // Offset the buffer by one byte so a 4-byte-aligned cast can't
+        // possibly start on an aligned address for at least one of the
+        // two possible base alignments.
+        let bytes: [u8; 9] = [0; 9];
+        let misaligned = &bytes[1..9];
+        let aligned_ptr_offset = misaligned.as_ptr() as usize % mem::align_of::<u32>();
+        if aligned_ptr_offset != 0 {
+            assert_eq!(
+                try_cast_slice::<u8, u32>(misaligned),
+                Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned)
+            );
+        }
+    }
+
+    #[test]
+    fn cast_slice_zero_sized_types_always_match() {
+        // `[u8; 0]` is a genuinely zero-sized `Pod` type.
+        let zsts: [[u8; 0]; 4] = [[], [], [], []];
+        let same: &[[u8; 0]] = cast_slice(&zsts[..]);
+        assert_eq!(same.len(), 4);
+    }
+
+    #[test]
+    fn cast_slice_mut_rejects_size_mismatch_between_zst_and_non_zst() {
+        let mut zsts: [[u8; 0]; 4] = [[], [], [], []];
+        assert_eq!(
+            try_cast_slice_mut::<[u8; 0], u8>(&mut zsts[..]),
+            Err(PodCastError::SizeMismatch)
+        );
+    }
+
+    #[test]
+    fn usage_discriminant_round_trips_the_tag() {
+        assert_eq!(Usage::GpuOnly.discriminant(), 0);
+        assert_eq!(Usage::Immutable.discriminant(), 1);
+        assert_eq!(Usage::Dynamic.discriminant(), 2);
+        assert_eq!(Usage::Persistent(Access::RW).discriminant(), 3);
+        assert_eq!(Usage::CpuOnly(Access::READ).discriminant(), 4);
+    }
+
+    #[test]
+    fn usage_is_valid_discriminant_rejects_out_of_range_tags() {
+        for tag in 0..=4u8 {
+            assert!(Usage::is_valid_discriminant(tag));
+        }
+        assert!(!Usage::is_valid_discriminant(5));
+        assert!(!Usage::is_valid_discriminant(255));
+    }
+
+    #[test]
+    fn checked_try_cast_slice_rejects_invalid_bool_bytes() {
+        let bytes: [u8; 4] = [0, 1, 0, 2];
+        assert!(checked::try_cast_slice::<u8, bool>(&bytes).is_err());
+        assert_eq!(
+            checked::try_cast_slice::<u8, bool>(&bytes[..3]).unwrap(),
+            &[false, true, false]
+        );
+    }
+
+    #[test]
+    fn checked_try_cast_slice_rejects_invalid_char_bytes() {
+        // A surrogate-half code point is not a valid `char`.
+        let surrogate: u32 = 0xD800;
+        let bytes = bytes_of(&surrogate);
+        assert!(checked::try_cast_slice::<u8, char>(bytes).is_err());
+
+        let valid: u32 = 'x' as u32;
+        let bytes = bytes_of(&valid);
+        assert_eq!(checked::try_cast_slice::<u8, char>(bytes).unwrap(), &['x']);
+    }
+
+    #[test]
+    fn usage_checked_bit_pattern_validates_tag_and_access() {
+        let valid = UsageBits { tag: 2, access: 0 };
+        assert!(Usage::is_valid_bit_pattern(&valid));
+
+        let bad_tag = UsageBits { tag: 9, access: 0 };
+        assert!(!Usage::is_valid_bit_pattern(&bad_tag));
+
+        let bad_access = UsageBits { tag: 3, access: 0xF };
+        assert!(!Usage::is_valid_bit_pattern(&bad_access));
+    }
+
+    #[test]
+    fn checked_try_cast_slice_propagates_slop() {
+        // Take the bytes from a `u32` so the input is aligned for `char`
+        // and only its length is wrong.
+        let word: u32 = 0;
+        let bytes = &bytes_of(&word)[..3];
+        match checked::try_cast_slice::<u8, char>(bytes) {
+            Err(checked::CheckedCastError::PodCastError(PodCastError::OutputSliceWouldHaveSlop)) => {}
+            other => panic!("expected OutputSliceWouldHaveSlop, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bytes_of_round_trips_through_from_bytes() {
+        let value: u32 = 0xdead_beef;
+        let bytes = bytes_of(&value);
+        assert_eq!(bytes.len(), mem::size_of::<u32>());
+        let back: &u32 = from_bytes(bytes);
+        assert_eq!(*back, value);
+    }
+
+    #[test]
+    fn bytes_of_mut_writes_are_visible_in_the_value() {
+        let mut value: u32 = 0;
+        {
+            let bytes = bytes_of_mut(&mut value);
+            bytes.copy_from_slice(&0x1020_3040u32.to_ne_bytes());
+        }
+        assert_eq!(value, 0x1020_3040);
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_size_mismatch() {
+        let bytes: [u8; 3] = [0; 3];
+        assert_eq!(
+            try_from_bytes::<u32>(&bytes),
+            Err(PodCastError::SizeMismatch)
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_misaligned_input() {
+        // Offset the buffer by one byte so a 4-byte-aligned value can't
+        // possibly start on an aligned address for at least one of the two
+        // possible base alignments.
+        let bytes: [u8; 5] = [0; 5];
+        let misaligned = &bytes[1..5];
+        if (misaligned.as_ptr() as usize) % mem::align_of::<u32>() != 0 {
+            assert_eq!(
+                try_from_bytes::<u32>(misaligned),
+                Err(PodCastError::AlignmentMismatch)
+            );
+        }
     }
 }